@@ -1,18 +1,22 @@
 use std::{
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
     error::Error,
     io::{BufReader, Stdout},
+    sync::mpsc::{self, Receiver},
     time::{Duration, Instant},
 };
 
 use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use lofty::{Accessor, AudioFile, TaggedFileExt};
 use rand::seq::SliceRandom;
-use rodio::Sink;
+use rodio::{Sink, Source};
+use souvlaki::{MediaControlEvent, MediaControls, MediaMetadata, MediaPlayback, PlatformConfig};
 use tui::{
     backend::CrosstermBackend,
     layout::{Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Cell, List, ListItem, ListState, Row, Table},
+    widgets::{Block, Borders, Cell, Gauge, List, ListItem, ListState, Paragraph, Row, Table},
     Frame, Terminal,
 };
 
@@ -33,32 +37,203 @@ impl<T> StatefulList<T> {
     }
 
     fn next(&mut self) {
-        let i = match self.state.selected() {
-            Some(i) => {
-                if i >= self.items.len() - 1 {
-                    0
-                } else {
-                    i + 1
-                }
-            }
-            None => 0,
-        };
-        self.state.select(Some(i));
+        let len = self.items.len();
+        self.next_in(len);
     }
 
     fn previous(&mut self) {
-        let i = match self.state.selected() {
-            Some(i) => {
-                if i == 0 {
-                    self.items.len() - 1
+        let len = self.items.len();
+        self.previous_in(len);
+    }
+
+    /// Like `next`/`previous`, but bounded by a caller-supplied length instead of
+    /// `items.len()`, so the selection can track a filtered view of the items.
+    fn next_in(&mut self, len: usize) {
+        list_select_next(&mut self.state, len);
+    }
+
+    fn previous_in(&mut self, len: usize) {
+        list_select_previous(&mut self.state, len);
+    }
+}
+
+/// Moves a `ListState` selection forward, wrapping at `len`. Shared by
+/// `StatefulList` and the plain `ListState`s that track non-`StatefulList`
+/// collections (e.g. the Queue).
+fn list_select_next(state: &mut ListState, len: usize) {
+    if len == 0 {
+        state.select(None);
+        return;
+    }
+    let i = match state.selected() {
+        Some(i) if i + 1 < len => i + 1,
+        _ => 0,
+    };
+    state.select(Some(i));
+}
+
+fn list_select_previous(state: &mut ListState, len: usize) {
+    if len == 0 {
+        state.select(None);
+        return;
+    }
+    let i = match state.selected() {
+        Some(0) | None => len - 1,
+        Some(i) => i - 1,
+    };
+    state.select(Some(i));
+}
+
+/// Swaps `state`'s selected entry in `queue` with its neighbor in
+/// `direction` (negative moves it up, positive moves it down), updating the
+/// selection to follow the moved entry. A no-op at either end of the queue.
+fn queue_move_selected(state: &mut ListState, queue: &mut VecDeque<String>, direction: isize) {
+    let Some(i) = state.selected() else {
+        return;
+    };
+    let len = queue.len();
+    let j = if direction < 0 {
+        if i == 0 {
+            return;
+        }
+        i - 1
+    } else {
+        if i + 1 >= len {
+            return;
+        }
+        i + 1
+    };
+    queue.swap(i, j);
+    state.select(Some(j));
+}
+
+/// Removes `state`'s selected entry from `queue`, clamping the selection to
+/// the new last entry (or clearing it if the queue is now empty).
+fn queue_remove_selected(state: &mut ListState, queue: &mut VecDeque<String>) {
+    let Some(i) = state.selected() else {
+        return;
+    };
+    if i >= queue.len() {
+        return;
+    }
+    queue.remove(i);
+    let len = queue.len();
+    state.select(if len == 0 { None } else { Some(i.min(len - 1)) });
+}
+
+#[derive(PartialEq)]
+enum InputMode {
+    Normal,
+    Search,
+}
+
+#[derive(PartialEq)]
+enum Focus {
+    Songs,
+    Queue,
+}
+
+/// Where the audio for a library entry actually comes from on disk. Most
+/// entries are a single file, but a `foo.intro.ogg` + `foo.loop.ogg` pair in
+/// `playrsources` is collapsed into one logical song "foo".
+#[derive(Clone)]
+enum SongSource {
+    Single(String),
+    Loop {
+        intro: Option<String>,
+        loop_file: String,
+    },
+}
+
+/// Metadata read from a track's ID3/Vorbis tags at `play()` time. The title
+/// falls back to the display name when absent; the other fields are `None`.
+#[derive(Clone)]
+struct TrackInfo {
+    title: String,
+    artist: Option<String>,
+    album: Option<String>,
+    duration: Option<Duration>,
+}
+
+/// The play state (stopped/playing/paused) and track metadata the Status
+/// panel shows, replacing the ad-hoc `is_paused` branching that used to live
+/// in `ui`. The history/loop/shuffle modifiers are read from `App` directly.
+#[derive(Clone)]
+enum PlayerStatus {
+    Stopped(Option<String>),
+    Playing(TrackInfo),
+    Paused(TrackInfo),
+}
+
+/// Scans `dir` for playable files, pairing up `*.intro.*`/`*.loop.*` files
+/// that share a base name into a single logical song. Returns the display
+/// names (for the Songs list) and a lookup from display name to the file(s)
+/// that actually back it.
+fn scan_song_library(dir: &str) -> (Vec<String>, HashMap<String, SongSource>) {
+    let mut intros: HashMap<String, String> = HashMap::new();
+    let mut loops: HashMap<String, String> = HashMap::new();
+    let mut singles: Vec<String> = Vec::new();
+
+    if let Ok(paths) = std::fs::read_dir(dir) {
+        for path in paths {
+            if let Some(filename) = path.ok().and_then(|p| p.file_name().into_string().ok()) {
+                if let Some(idx) = filename.find(".intro.") {
+                    intros.insert(filename[..idx].to_string(), filename);
+                } else if let Some(idx) = filename.find(".loop.") {
+                    loops.insert(filename[..idx].to_string(), filename);
                 } else {
-                    i - 1
+                    singles.push(filename);
                 }
             }
-            None => 0,
-        };
-        self.state.select(Some(i));
+        }
+    }
+
+    let mut names = Vec::new();
+    let mut sources = HashMap::new();
+
+    for filename in singles {
+        sources.insert(filename.clone(), SongSource::Single(filename.clone()));
+        names.push(filename);
     }
+
+    for (base, loop_file) in loops {
+        let intro = intros.remove(&base);
+        names.push(base.clone());
+        sources.insert(base, SongSource::Loop { intro, loop_file });
+    }
+
+    // An intro file without a matching loop segment isn't a valid pair; play it as-is.
+    for (base, intro_file) in intros {
+        names.push(base.clone());
+        sources.insert(base, SongSource::Single(intro_file));
+    }
+
+    (names, sources)
+}
+
+/// `items` matching `query`, ranked best-first by fuzzy score. Returns a
+/// clone of `items` unchanged when `query` is empty.
+fn fuzzy_filter(items: &[String], query: &str) -> Vec<String> {
+    if query.is_empty() {
+        return items.to_vec();
+    }
+    let matcher = SkimMatcherV2::default();
+    let mut scored: Vec<(i64, &String)> = items
+        .iter()
+        .filter_map(|item| matcher.fuzzy_match(item, query).map(|score| (score, item)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.into_iter().map(|(_, item)| item.clone()).collect()
+}
+
+/// The index of `song` within `fuzzy_filter(items, query)`, or `None` if
+/// `song` is `None` or no longer present in that view (e.g. it fell out of
+/// `items` entirely). Used to re-point a selection at the same song across a
+/// query change, since a raw `ListState` index doesn't survive the view
+/// being re-sorted or re-filtered.
+fn visible_index_of(items: &[String], query: &str, song: Option<&str>) -> Option<usize> {
+    let visible = fuzzy_filter(items, query);
+    song.and_then(|song| visible.iter().position(|s| s == song))
 }
 
 pub struct App<'a> {
@@ -68,24 +243,61 @@ pub struct App<'a> {
     looping: bool,
     shuffle: bool,
     playing: Option<String>,
+    total_duration: Option<Duration>,
+    input_mode: InputMode,
+    query: String,
+    history: Vec<String>,
+    history_index: usize,
+    focus: Focus,
+    queue_state: ListState,
+    media_controls: Option<MediaControls>,
+    media_events: Option<Receiver<MediaControlEvent>>,
+    /// Why `media_controls`/`media_events` are `None`, shown in the Status
+    /// panel since `App::new` runs after the terminal has already entered
+    /// raw/alternate-screen mode and can't just eprintln! the failure.
+    media_error: Option<String>,
+    song_sources: HashMap<String, SongSource>,
+    track_info: Option<TrackInfo>,
+    active_loop: Option<rodio::source::Buffered<rodio::Decoder<BufReader<std::fs::File>>>>,
 }
 
 
 
 impl<'a> App<'a> {
     pub fn new(sink: &mut Sink) -> App {
-        let mut songs: Vec<String> = vec![];
-        if let Ok(paths) = std::fs::read_dir("playrsources") {
-            for path in paths {
-                if let Some(s) = path
-                    .map(|o| o.file_name().into_string().ok())
-                    .ok()
-                    .flatten()
-                {
-                    songs.push(s);
+        let (songs, song_sources) = scan_song_library("playrsources");
+
+        // OS media-key/MPRIS integration is a nice-to-have: headless, SSH, and
+        // container environments commonly have no D-Bus/media session to
+        // attach to, so failing here must not take down the whole TUI. `App`
+        // is constructed after the terminal has already entered raw mode and
+        // the alternate screen, so a failure here can't be eprintln!'d — it
+        // would corrupt the draw or simply vanish on exit. Remember it and
+        // surface it through the Status panel instead.
+        let (media_controls, media_events, media_error) = match MediaControls::new(PlatformConfig {
+            dbus_name: "playr",
+            display_name: "Playr",
+            hwnd: None,
+        }) {
+            Ok(mut controls) => {
+                let (media_tx, media_events) = mpsc::channel();
+                match controls.attach(move |event| {
+                    let _ = media_tx.send(event);
+                }) {
+                    Ok(()) => (Some(controls), Some(media_events), None),
+                    Err(err) => (
+                        None,
+                        None,
+                        Some(format!("media controls unavailable: {:?}", err)),
+                    ),
                 }
             }
-        }
+            Err(err) => (
+                None,
+                None,
+                Some(format!("media controls unavailable: {:?}", err)),
+            ),
+        };
 
         App {
             songs: StatefulList::with_items(songs),
@@ -94,6 +306,19 @@ impl<'a> App<'a> {
             looping: false,
             shuffle: false,
             playing: None,
+            total_duration: None,
+            input_mode: InputMode::Normal,
+            query: String::new(),
+            history: Vec::new(),
+            history_index: 0,
+            focus: Focus::Songs,
+            queue_state: ListState::default(),
+            media_controls,
+            media_events,
+            media_error,
+            song_sources,
+            track_info: None,
+            active_loop: None,
         }
     }
 
@@ -107,8 +332,13 @@ impl<'a> App<'a> {
         loop {
             // handle playing the next song and looping
             if self.sink.len() == 0 {
-                // playing should still be stored here so can we make sure we dont play the same song twice
-                if self.shuffle {
+                // if we're replaying from history, walk forward through it before
+                // falling back to shuffle/loop/queue behavior
+                if self.history_index + 1 < self.history.len() {
+                    self.history_index += 1;
+                    let next = self.history[self.history_index].clone();
+                    self.replay_history_track(next);
+                } else if self.shuffle {
                     let mut previous_or_to_play: String = self.playing.as_ref().map(|v| v.clone()).unwrap_or("".to_string());
                     loop {
                         if self.songs.items.len() <= 1 {
@@ -122,6 +352,14 @@ impl<'a> App<'a> {
                         }
                     }
                     self.play(previous_or_to_play);
+                } else if self.looping && self.active_loop.is_some() {
+                    // Continuation of an intro+loop track: re-queue the
+                    // already-buffered loop segment instead of re-decoding it
+                    // (the sink has already drained, so this is not gapless),
+                    // re-checking `looping` each time so toggling it off takes
+                    // effect as soon as the current pass finishes.
+                    let clip = self.active_loop.clone().unwrap();
+                    self.sink.append(clip);
                 } else if self.looping && self.playing.is_some() {
                     let current = self.playing.as_ref().unwrap();
                     self.play(current.clone());
@@ -135,6 +373,9 @@ impl<'a> App<'a> {
                 }
             }
 
+            self.handle_media_events();
+            self.sync_media_controls();
+
             terminal.draw(|f| ui(f, self))?;
 
             let timeout = tick_rate
@@ -143,10 +384,56 @@ impl<'a> App<'a> {
             if crossterm::event::poll(timeout)? {
                 if let Event::Key(key) = event::read()? {
                     if key.kind == KeyEventKind::Press {
+                        if self.input_mode == InputMode::Search {
+                            match key.code {
+                                KeyCode::Esc => {
+                                    let selected = self.get_selected();
+                                    self.input_mode = InputMode::Normal;
+                                    self.query.clear();
+                                    self.reselect_song(selected);
+                                }
+                                KeyCode::Char(c) => {
+                                    self.query.push(c);
+                                    self.select_best_match();
+                                }
+                                KeyCode::Backspace => {
+                                    self.query.pop();
+                                    self.select_best_match();
+                                }
+                                KeyCode::Down => {
+                                    let len = self.visible_songs().len();
+                                    self.songs.next_in(len);
+                                }
+                                KeyCode::Up => {
+                                    let len = self.visible_songs().len();
+                                    self.songs.previous_in(len);
+                                }
+                                KeyCode::Enter => {
+                                    if key.modifiers.contains(KeyModifiers::SHIFT) {
+                                        self.queue_selected();
+                                    } else {
+                                        self.play_selected();
+                                    }
+                                    self.sink.play();
+                                }
+                                _ => {}
+                            }
+                            continue;
+                        }
                         match key.code {
                             KeyCode::Char('q') => return Ok(()),
-                            KeyCode::Down => self.songs.next(),
-                            KeyCode::Up => self.songs.previous(),
+                            KeyCode::Char('/') => {
+                                self.input_mode = InputMode::Search;
+                                self.query.clear();
+                            }
+                            KeyCode::Down => match self.focus {
+                                Focus::Songs => self.songs.next(),
+                                Focus::Queue => list_select_next(&mut self.queue_state, self.queue.len()),
+                            },
+                            KeyCode::Up => match self.focus {
+                                Focus::Songs => self.songs.previous(),
+                                Focus::Queue => list_select_previous(&mut self.queue_state, self.queue.len()),
+                            },
                             KeyCode::Enter => {
                                 if key.modifiers.contains(KeyModifiers::SHIFT) {
                                     self.queue_selected();
@@ -170,6 +457,12 @@ impl<'a> App<'a> {
                                 self.sink.play();
                             },
                             KeyCode::Tab => {
+                                self.focus = match self.focus {
+                                    Focus::Songs => Focus::Queue,
+                                    Focus::Queue => Focus::Songs,
+                                };
+                            },
+                            KeyCode::Char('s') => {
                                 self.shuffle = !self.shuffle;
                             },
                             KeyCode::Right => {
@@ -184,6 +477,18 @@ impl<'a> App<'a> {
                                         / 100.0,
                                 );
                             }
+                            KeyCode::Char(',') => self.seek_by(-5),
+                            KeyCode::Char('.') => self.seek_by(5),
+                            KeyCode::Char('p') => self.play_previous(),
+                            KeyCode::Char('J') if self.focus == Focus::Queue => {
+                                self.move_queue_selected(1)
+                            }
+                            KeyCode::Char('K') if self.focus == Focus::Queue => {
+                                self.move_queue_selected(-1)
+                            }
+                            KeyCode::Char('d') if self.focus == Focus::Queue => {
+                                self.remove_queue_selected()
+                            }
                             _ => {}
                         }
                     }
@@ -196,10 +501,202 @@ impl<'a> App<'a> {
     }
 
     fn play(&mut self, song: String) {
-        let file = std::fs::File::open(format!("playrsources/{}", song)).unwrap();
-        self.sink
-            .append(rodio::Decoder::new(BufReader::new(file)).unwrap());
-        self.playing = Some(song)
+        self.append_song_audio(&song);
+        self.playing = Some(song.clone());
+        self.history.push(song);
+        self.history_index = self.history.len() - 1;
+    }
+
+    /// Re-appends an already-known history entry to the `Sink` without
+    /// pushing a duplicate onto `history`.
+    fn replay_history_track(&mut self, song: String) {
+        self.append_song_audio(&song);
+        self.playing = Some(song);
+    }
+
+    /// Appends the audio for `song` to the `Sink`, branching on whether it's a
+    /// plain file or an intro+loop pair, and updates `total_duration` and
+    /// `track_info` to match.
+    fn append_song_audio(&mut self, song: &str) {
+        let tag_file = match self.song_sources.get(song).cloned() {
+            Some(SongSource::Loop { intro, loop_file }) => {
+                let tag_file = intro.clone().unwrap_or_else(|| loop_file.clone());
+                self.append_loop_audio(&intro, &loop_file);
+                tag_file
+            }
+            Some(SongSource::Single(filename)) => {
+                self.append_single_audio(&filename);
+                filename
+            }
+            None => {
+                self.append_single_audio(song);
+                song.to_string()
+            }
+        };
+        self.track_info = Some(Self::read_track_info(song, &tag_file));
+    }
+
+    /// Reads title/artist/album/duration from `filename`'s tags, falling back
+    /// to `display_name` for the title when tags are absent or unreadable.
+    fn read_track_info(display_name: &str, filename: &str) -> TrackInfo {
+        let path = format!("playrsources/{}", filename);
+        let tagged_file = lofty::read_from_path(&path).ok();
+        let tag = tagged_file
+            .as_ref()
+            .and_then(|f| f.primary_tag().or_else(|| f.first_tag()));
+
+        TrackInfo {
+            title: tag
+                .and_then(|t| t.title().map(|s| s.to_string()))
+                .unwrap_or_else(|| display_name.to_string()),
+            artist: tag.and_then(|t| t.artist().map(|s| s.to_string())),
+            album: tag.and_then(|t| t.album().map(|s| s.to_string())),
+            duration: tagged_file.as_ref().map(|f| f.properties().duration()),
+        }
+    }
+
+    /// The current playback state and, when a track is loaded, its metadata,
+    /// as the Status panel renders it.
+    fn player_status(&self) -> PlayerStatus {
+        match (&self.playing, &self.track_info) {
+            (Some(_), Some(info)) => {
+                if self.sink.is_paused() {
+                    PlayerStatus::Paused(info.clone())
+                } else {
+                    PlayerStatus::Playing(info.clone())
+                }
+            }
+            _ => PlayerStatus::Stopped(self.history.last().cloned()),
+        }
+    }
+
+    fn append_single_audio(&mut self, filename: &str) {
+        let file = std::fs::File::open(format!("playrsources/{}", filename)).unwrap();
+        let decoder = rodio::Decoder::new(BufReader::new(file)).unwrap();
+        self.total_duration = decoder.total_duration();
+        self.sink.append(decoder);
+        self.active_loop = None;
+    }
+
+    /// Appends the (optional) intro followed by the loop segment. When
+    /// looping is enabled the loop segment is decoded once, buffered, and
+    /// kept in `active_loop` so `run` can re-append it, without re-reading
+    /// it from disk, each time the `Sink` drains. The intro-to-loop handoff
+    /// is gapless; each repeat of the loop is not: `run` only sees the empty
+    /// `Sink` on its next pass of the event loop, so the loop point can have
+    /// up to one tick of silence. `run` re-checks `self.looping` on every pass instead of
+    /// repeating it forever with `repeat_infinite` — that would keep
+    /// `Sink::len()` from ever reaching 0, which blocks every auto-advance
+    /// path (toggling loop off, shuffle, queue resume, history) that's
+    /// gated on the sink being empty. Otherwise it's appended once like any
+    /// other song.
+    fn append_loop_audio(&mut self, intro: &Option<String>, loop_file: &str) {
+        let mut total = Duration::from_secs(0);
+        if let Some(intro_name) = intro {
+            let file = std::fs::File::open(format!("playrsources/{}", intro_name)).unwrap();
+            let decoder = rodio::Decoder::new(BufReader::new(file)).unwrap();
+            total += decoder.total_duration().unwrap_or_default();
+            self.sink.append(decoder);
+        }
+
+        let file = std::fs::File::open(format!("playrsources/{}", loop_file)).unwrap();
+        let decoder = rodio::Decoder::new(BufReader::new(file)).unwrap();
+        if self.looping {
+            let buffered = decoder.buffered();
+            self.sink.append(buffered.clone());
+            self.active_loop = Some(buffered);
+            self.total_duration = None;
+        } else {
+            total += decoder.total_duration().unwrap_or_default();
+            self.sink.append(decoder);
+            self.total_duration = Some(total);
+            self.active_loop = None;
+        }
+    }
+
+    fn play_previous(&mut self) {
+        if self.history_index == 0 {
+            return;
+        }
+        self.history_index -= 1;
+        let song = self.history[self.history_index].clone();
+        self.sink.stop();
+        self.replay_history_track(song);
+        self.sink.play();
+    }
+
+    fn is_replaying_history(&self) -> bool {
+        self.history_index + 1 < self.history.len()
+    }
+
+    /// Swaps the selected Queue entry with its neighbor in `direction`
+    /// (negative moves it up, positive moves it down).
+    fn move_queue_selected(&mut self, direction: isize) {
+        queue_move_selected(&mut self.queue_state, &mut self.queue, direction);
+    }
+
+    fn remove_queue_selected(&mut self) {
+        queue_remove_selected(&mut self.queue_state, &mut self.queue);
+    }
+
+    /// Drains pending OS media-key/MPRIS events and applies them to the `Sink`
+    /// the same way the matching keyboard shortcut would. A no-op when media
+    /// controls failed to initialize.
+    fn handle_media_events(&mut self) {
+        let Some(rx) = &self.media_events else {
+            return;
+        };
+        let events: Vec<MediaControlEvent> = rx.try_iter().collect();
+        for event in events {
+            match event {
+                MediaControlEvent::Play => self.sink.play(),
+                MediaControlEvent::Pause => self.sink.pause(),
+                MediaControlEvent::Toggle => {
+                    if self.sink.is_paused() {
+                        self.sink.play();
+                    } else {
+                        self.sink.pause();
+                    }
+                }
+                MediaControlEvent::Next => {
+                    self.sink.stop();
+                    self.sink.play();
+                }
+                MediaControlEvent::Previous => self.play_previous(),
+                MediaControlEvent::SetVolume(volume) => self.sink.set_volume(volume as f32),
+                MediaControlEvent::Stop => self.sink.stop(),
+                _ => {}
+            }
+        }
+    }
+
+    /// Publishes the current track and playback status to the OS so hardware
+    /// media keys and desktop widgets stay in sync with the TUI. A no-op when
+    /// media controls failed to initialize.
+    fn sync_media_controls(&mut self) {
+        let Some(controls) = &mut self.media_controls else {
+            return;
+        };
+        let _ = controls.set_metadata(MediaMetadata {
+            title: self.playing.as_deref(),
+            ..Default::default()
+        });
+        let playback = match self.player_status() {
+            PlayerStatus::Stopped(_) => MediaPlayback::Stopped,
+            PlayerStatus::Paused(_) => MediaPlayback::Paused { progress: None },
+            PlayerStatus::Playing(_) => MediaPlayback::Playing { progress: None },
+        };
+        let _ = controls.set_playback(playback);
+    }
+
+    fn seek_by(&mut self, offset_secs: i64) {
+        let current = self.sink.get_pos();
+        let target = if offset_secs.is_negative() {
+            current.saturating_sub(Duration::from_secs(offset_secs.unsigned_abs()))
+        } else {
+            current + Duration::from_secs(offset_secs as u64)
+        };
+        let _ = self.sink.try_seek(target);
     }
 
     fn play_selected(&mut self) {
@@ -215,11 +712,48 @@ impl<'a> App<'a> {
     }
 
     fn get_selected(&mut self) -> Option<String> {
+        let visible = self.visible_songs();
         self.songs
             .state
             .selected()
-            .map(|ind| self.songs.items.get(ind).map(|v| v.clone()))
-            .flatten()
+            .and_then(|ind| visible.get(ind).cloned())
+    }
+
+    /// The songs currently shown in the Songs list: all of them in Normal mode,
+    /// or those fuzzy-matching `query` ranked best-first while searching.
+    fn visible_songs(&self) -> Vec<String> {
+        fuzzy_filter(&self.songs.items, &self.query)
+    }
+
+    /// Re-points the Songs selection at the top-ranked fuzzy match for the
+    /// current query, called whenever the query changes.
+    fn select_best_match(&mut self) {
+        let len = self.visible_songs().len();
+        self.songs.state.select(if len == 0 { None } else { Some(0) });
+    }
+
+    /// Re-points the Songs selection at `song` in the *current* `visible_songs()`
+    /// view. Used when leaving search, since the raw index selected against the
+    /// filtered, score-sorted view doesn't point at the same song once the view
+    /// reverts to the unfiltered list.
+    fn reselect_song(&mut self, song: Option<String>) {
+        let index = visible_index_of(&self.songs.items, &self.query, song.as_deref());
+        self.songs.state.select(index);
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let total_secs = duration.as_secs();
+    format!("{:02}:{:02}", total_secs / 60, total_secs % 60)
+}
+
+/// A bordered block titled `title`, highlighted when its panel has focus.
+fn focusable_block<'a>(title: &'a str, focused: bool) -> Block<'a> {
+    let block = Block::default().borders(Borders::ALL).title(title);
+    if focused {
+        block.border_style(Style::default().fg(Color::Yellow))
+    } else {
+        block
     }
 }
 
@@ -247,80 +781,370 @@ fn ui(f: &mut Frame<CrosstermBackend<Stdout>>, app: &mut App) {
         )
         .split(chunks[1]);
 
+    let songs_area = if app.input_mode == InputMode::Search {
+        let search_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+            .split(chunks[0]);
+        let input_block = Paragraph::new(app.query.as_str())
+            .block(Block::default().borders(Borders::ALL).title("Search"));
+        f.render_widget(input_block, search_chunks[0]);
+        search_chunks[1]
+    } else {
+        chunks[0]
+    };
+
     let songs: Vec<ListItem> = app
-        .songs
-        .items
+        .visible_songs()
         .iter()
         .map(|e| ListItem::new(e.clone()))
         .collect();
     let songs_block = List::new(songs)
-        .block(Block::default().borders(Borders::ALL).title("Songs"))
+        .block(focusable_block("Songs", app.focus == Focus::Songs))
         .highlight_style(
             Style::default()
                 .bg(Color::LightGreen)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol(">> ");
-    f.render_stateful_widget(songs_block, chunks[0], &mut app.songs.state);
+    f.render_stateful_widget(songs_block, songs_area, &mut app.songs.state);
 
     let rows: Vec<Row> = vec![
         Row::new(vec![Cell::from("Play"), Cell::from("Enter")]),
         Row::new(vec![Cell::from("Queue"), Cell::from("Shift + Enter")]),
         Row::new(vec![Cell::from("Pause"), Cell::from("Space")]),
         Row::new(vec![Cell::from("Loop"), Cell::from("=")]),
-        Row::new(vec![Cell::from("Shuffle"), Cell::from("Tab")]),
+        Row::new(vec![Cell::from("Shuffle"), Cell::from("s")]),
+        Row::new(vec![Cell::from("Switch Focus"), Cell::from("Tab")]),
+        Row::new(vec![Cell::from("Move Queue Item"), Cell::from("J / K")]),
+        Row::new(vec![Cell::from("Remove Queue Item"), Cell::from("d")]),
         Row::new(vec![Cell::from("Skip"), Cell::from("Backspace")]),
         Row::new(vec![Cell::from("Volume Up"), Cell::from("Right Arrow")]),
         Row::new(vec![Cell::from("Volume Down"), Cell::from("Left Arrow")]),
+        Row::new(vec![Cell::from("Seek Back"), Cell::from(",")]),
+        Row::new(vec![Cell::from("Seek Forward"), Cell::from(".")]),
+        Row::new(vec![Cell::from("Search"), Cell::from("/")]),
+        Row::new(vec![Cell::from("Previous Track"), Cell::from("p")]),
     ];
     let controls_block = Table::new(rows)
         .block(Block::default().borders(Borders::ALL).title("Controls"))
         .widths(&[Constraint::Percentage(50), Constraint::Percentage(50)]);
     f.render_widget(controls_block, right_chunks[0]);
 
-    let rows: Vec<Row> = vec![
-        if app.sink.is_paused() {
-            Row::new(vec![Cell::from("Paused"), Cell::from("")])
-        } else if app.looping {
-            Row::new(vec![
-                Cell::from("Looping"),
-                Cell::from(format!(
-                    "{}",
-                    app.playing
-                        .as_ref()
-                        .map(|p| p.clone())
-                        .unwrap_or("None".to_string())
-                )),
-            ])
-        } else if app.shuffle {
-            Row::new(vec![Cell::from("Shuffling"), Cell::from("")])
+    let status = app.player_status();
+    let status_label = {
+        let verb = match status {
+            PlayerStatus::Stopped(_) => "Stopped",
+            PlayerStatus::Playing(_) => "Playing",
+            PlayerStatus::Paused(_) => "Paused",
+        };
+        let mut modifiers = Vec::new();
+        if app.is_replaying_history() {
+            modifiers.push("history");
+        }
+        if app.looping {
+            modifiers.push("loop");
+        }
+        if app.shuffle {
+            modifiers.push("shuffle");
+        }
+        if modifiers.is_empty() {
+            verb.to_string()
         } else {
-            Row::new(vec![
-                Cell::from("Playing"),
-                Cell::from(format!(
-                    "{}",
-                    app.playing
-                        .as_ref()
-                        .map(|p| p.clone())
-                        .unwrap_or("None".to_string())
-                )),
-            ])
-        },
+            format!("{} ({})", verb, modifiers.join(", "))
+        }
+    };
+    let (title, artist, album, duration) = match &status {
+        PlayerStatus::Playing(info) | PlayerStatus::Paused(info) => (
+            info.title.clone(),
+            info.artist.clone().unwrap_or_else(|| "-".to_string()),
+            info.album.clone().unwrap_or_else(|| "-".to_string()),
+            info.duration.map(format_duration).unwrap_or_else(|| "-".to_string()),
+        ),
+        PlayerStatus::Stopped(last) => (
+            last.clone().unwrap_or_else(|| "None".to_string()),
+            "-".to_string(),
+            "-".to_string(),
+            "-".to_string(),
+        ),
+    };
+    let rows: Vec<Row> = vec![
+        Row::new(vec![Cell::from("Status"), Cell::from(status_label)]),
+        Row::new(vec![Cell::from("Title"), Cell::from(title)]),
+        Row::new(vec![Cell::from("Artist"), Cell::from(artist)]),
+        Row::new(vec![Cell::from("Album"), Cell::from(album)]),
+        Row::new(vec![Cell::from("Duration"), Cell::from(duration)]),
         Row::new(vec![
             Cell::from("Volume"),
             Cell::from(format!("{}%", (app.sink.volume() * 100.0) as usize)),
         ]),
     ];
+    let rows: Vec<Row> = if let Some(media_error) = &app.media_error {
+        rows.into_iter()
+            .chain(std::iter::once(Row::new(vec![
+                Cell::from("Media Keys"),
+                Cell::from(media_error.clone()),
+            ])))
+            .collect()
+    } else {
+        rows
+    };
+    let status_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)].as_ref())
+        .split(right_chunks[1]);
+
     let status_block = Table::new(rows)
         .block(Block::default().borders(Borders::ALL).title("Status"))
         .widths(&[Constraint::Percentage(50), Constraint::Percentage(50)]);
-    f.render_widget(status_block, right_chunks[1]);
+    f.render_widget(status_block, status_chunks[0]);
 
-    while app.queue.len() > app.sink.len() {
-        app.queue.pop_front();
+    let elapsed = app.sink.get_pos();
+    let total = app.total_duration.unwrap_or(Duration::from_secs(0));
+    let ratio = if total.as_secs_f64() > 0.0 {
+        (elapsed.as_secs_f64() / total.as_secs_f64()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let progress_label = format!(
+        "{} / {}",
+        format_duration(elapsed),
+        format_duration(total)
+    );
+    let progress_block = Gauge::default()
+        .block(Block::default().borders(Borders::ALL).title("Progress"))
+        .gauge_style(Style::default().fg(Color::LightGreen))
+        .label(progress_label)
+        .ratio(ratio);
+    f.render_widget(progress_block, status_chunks[1]);
+
+    let queue_len = app.queue.len();
+    if let Some(i) = app.queue_state.selected() {
+        if queue_len == 0 {
+            app.queue_state.select(None);
+        } else if i >= queue_len {
+            app.queue_state.select(Some(queue_len - 1));
+        }
     }
     let queue_list: Vec<ListItem> = app.queue.iter().map(|v| ListItem::new(v.clone())).collect();
-    let queue_block =
-        List::new(queue_list).block(Block::default().borders(Borders::ALL).title("Queue"));
-    f.render_widget(queue_block, right_chunks[2]);
+    let queue_block = List::new(queue_list)
+        .block(focusable_block("Queue", app.focus == Focus::Queue))
+        .highlight_style(
+            Style::default()
+                .bg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+    f.render_stateful_widget(queue_block, right_chunks[2], &mut app.queue_state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_select_next_wraps_around() {
+        let mut state = ListState::default();
+        state.select(Some(0));
+        list_select_next(&mut state, 3);
+        assert_eq!(state.selected(), Some(1));
+        list_select_next(&mut state, 3);
+        assert_eq!(state.selected(), Some(2));
+        list_select_next(&mut state, 3);
+        assert_eq!(state.selected(), Some(0));
+    }
+
+    #[test]
+    fn list_select_next_on_empty_list_clears_selection() {
+        let mut state = ListState::default();
+        state.select(Some(0));
+        list_select_next(&mut state, 0);
+        assert_eq!(state.selected(), None);
+    }
+
+    #[test]
+    fn list_select_previous_wraps_around() {
+        let mut state = ListState::default();
+        state.select(Some(0));
+        list_select_previous(&mut state, 3);
+        assert_eq!(state.selected(), Some(2));
+        list_select_previous(&mut state, 3);
+        assert_eq!(state.selected(), Some(1));
+    }
+
+    #[test]
+    fn list_select_previous_with_no_selection_lands_on_last() {
+        let mut state = ListState::default();
+        list_select_previous(&mut state, 3);
+        assert_eq!(state.selected(), Some(2));
+    }
+
+    #[test]
+    fn queue_move_selected_swaps_with_neighbor() {
+        let mut state = ListState::default();
+        state.select(Some(0));
+        let mut queue: VecDeque<String> = vec!["a".to_string(), "b".to_string(), "c".to_string()]
+            .into_iter()
+            .collect();
+        queue_move_selected(&mut state, &mut queue, 1);
+        assert_eq!(queue, vec!["b".to_string(), "a".to_string(), "c".to_string()]);
+        assert_eq!(state.selected(), Some(1));
+    }
+
+    #[test]
+    fn queue_move_selected_is_noop_at_boundary() {
+        let mut state = ListState::default();
+        state.select(Some(0));
+        let mut queue: VecDeque<String> = vec!["a".to_string(), "b".to_string()].into_iter().collect();
+        queue_move_selected(&mut state, &mut queue, -1);
+        assert_eq!(queue, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(state.selected(), Some(0));
+    }
+
+    #[test]
+    fn queue_remove_selected_clamps_to_new_last_entry() {
+        let mut state = ListState::default();
+        state.select(Some(2));
+        let mut queue: VecDeque<String> = vec!["a".to_string(), "b".to_string(), "c".to_string()]
+            .into_iter()
+            .collect();
+        queue_remove_selected(&mut state, &mut queue);
+        assert_eq!(queue, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(state.selected(), Some(1));
+    }
+
+    #[test]
+    fn queue_remove_selected_clears_selection_when_queue_empties() {
+        let mut state = ListState::default();
+        state.select(Some(0));
+        let mut queue: VecDeque<String> = vec!["a".to_string()].into_iter().collect();
+        queue_remove_selected(&mut state, &mut queue);
+        assert!(queue.is_empty());
+        assert_eq!(state.selected(), None);
+    }
+
+    #[test]
+    fn fuzzy_filter_with_empty_query_returns_all_in_order() {
+        let items = vec!["b.ogg".to_string(), "a.ogg".to_string()];
+        assert_eq!(fuzzy_filter(&items, ""), items);
+    }
+
+    #[test]
+    fn fuzzy_filter_ranks_best_match_first() {
+        let items = vec![
+            "unrelated.ogg".to_string(),
+            "adventure_theme.ogg".to_string(),
+        ];
+        let filtered = fuzzy_filter(&items, "advthm");
+        assert_eq!(filtered.first(), Some(&"adventure_theme.ogg".to_string()));
+    }
+
+    #[test]
+    fn fuzzy_filter_excludes_non_matches() {
+        let items = vec!["adventure_theme.ogg".to_string(), "boss_fight.ogg".to_string()];
+        let filtered = fuzzy_filter(&items, "zzz");
+        assert!(filtered.is_empty());
+    }
+
+    #[test]
+    fn visible_index_of_follows_song_identity_back_to_the_unfiltered_list() {
+        // Regression test for leaving search (Esc): the selected song was
+        // highlighted at index 0 of the filtered, score-sorted view, but it
+        // sits at a different index once the view reverts to the full list.
+        let items = vec![
+            "alpha.ogg".to_string(),
+            "beta.ogg".to_string(),
+            "gamma.ogg".to_string(),
+        ];
+        let filtered = fuzzy_filter(&items, "gam");
+        assert_eq!(filtered, vec!["gamma.ogg".to_string()]);
+
+        let selected_song = filtered.get(0).cloned();
+        let index = visible_index_of(&items, "", selected_song.as_deref());
+        assert_eq!(index, Some(2));
+    }
+
+    #[test]
+    fn visible_index_of_clears_selection_when_song_is_gone() {
+        let items = vec!["alpha.ogg".to_string(), "beta.ogg".to_string()];
+        assert_eq!(visible_index_of(&items, "", Some("missing.ogg")), None);
+        assert_eq!(visible_index_of(&items, "", None), None);
+    }
+
+    /// Creates a throwaway directory under the OS temp dir for `scan_song_library`
+    /// to scan, cleaning it up when the returned guard drops.
+    struct TempDir(std::path::PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            let path = std::env::temp_dir().join(format!("playr-test-{}-{}", name, std::process::id()));
+            std::fs::create_dir_all(&path).unwrap();
+            TempDir(path)
+        }
+
+        fn touch(&self, filename: &str) {
+            std::fs::write(self.0.join(filename), []).unwrap();
+        }
+
+        fn path(&self) -> &str {
+            self.0.to_str().unwrap()
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn scan_song_library_pairs_intro_and_loop_files() {
+        let dir = TempDir::new("pairs");
+        dir.touch("theme.intro.ogg");
+        dir.touch("theme.loop.ogg");
+        dir.touch("menu.ogg");
+
+        let (names, sources) = scan_song_library(dir.path());
+
+        assert!(names.contains(&"theme".to_string()));
+        assert!(names.contains(&"menu.ogg".to_string()));
+        match sources.get("theme") {
+            Some(SongSource::Loop { intro, loop_file }) => {
+                assert_eq!(intro.as_deref(), Some("theme.intro.ogg"));
+                assert_eq!(loop_file, "theme.loop.ogg");
+            }
+            other => panic!("expected a Loop source, got {:?}", other.is_some()),
+        }
+    }
+
+    #[test]
+    fn scan_song_library_treats_orphan_loop_as_introless() {
+        let dir = TempDir::new("orphan-loop");
+        dir.touch("ambient.loop.ogg");
+
+        let (names, sources) = scan_song_library(dir.path());
+
+        assert_eq!(names, vec!["ambient".to_string()]);
+        match sources.get("ambient") {
+            Some(SongSource::Loop { intro, loop_file }) => {
+                assert!(intro.is_none());
+                assert_eq!(loop_file, "ambient.loop.ogg");
+            }
+            other => panic!("expected a Loop source, got {:?}", other.is_some()),
+        }
+    }
+
+    #[test]
+    fn scan_song_library_treats_orphan_intro_as_single() {
+        let dir = TempDir::new("orphan-intro");
+        dir.touch("fanfare.intro.ogg");
+
+        let (names, sources) = scan_song_library(dir.path());
+
+        assert_eq!(names, vec!["fanfare".to_string()]);
+        match sources.get("fanfare") {
+            Some(SongSource::Single(filename)) => assert_eq!(filename, "fanfare.intro.ogg"),
+            other => panic!("expected a Single source, got {:?}", other.is_some()),
+        }
+    }
 }